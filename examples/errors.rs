@@ -0,0 +1,48 @@
+//! A CLI that reports parse errors and exits with a failure code.
+//!
+//! ```text
+//! cargo run --example errors -- --port 70000
+//! ```
+
+use parsg::prelude::*;
+
+fn parser() -> Parser {
+    let mut parser = Parser::new("serve".to_string(), "Serves the current directory".to_string(), "serve --port 8080".to_string());
+    parser.add_command("port".to_string(), true, "p".to_string(), "The port to listen on".to_string()).value_type(ValueType::Int).range(1..=65535);
+    parser
+}
+
+fn run(args: Vec<String>) -> Result<i64, String> {
+    let mut parser = parser();
+    match parser.parse_typed(args.join(" ")) {
+        Ok(values) => match values.get("port") {
+            Some(Value::Int(port)) => Ok(*port),
+            _ => Ok(8080),
+        },
+        Err(ParseError::HelpRequested(help)) => Err(help),
+        Err(error) => Err(parser.format_error(&error)),
+    }
+}
+
+fn main() {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(port) => println!("listening on port {}", port),
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(run(vec![]), Ok(8080));
+        assert_eq!(run(vec!["-p".to_string(), "3000".to_string()]), Ok(3000));
+        assert_eq!(run(vec!["--port".to_string(), "70000".to_string()]), Err("serve: value 70000 for --port is out of range 1..=65535: above the maximum of 65535".to_string()));
+        assert_eq!(run(vec!["--host".to_string()]), Err("serve: Invalid argument: --host".to_string()));
+    }
+}
@@ -0,0 +1,40 @@
+//! A small CLI taking a flag and a value.
+//!
+//! ```text
+//! cargo run --example greet -- --name=John --shout
+//! ```
+
+use parsg::prelude::*;
+
+fn parser() -> Parser {
+    let mut parser = Parser::new("greet".to_string(), "Greets someone".to_string(), "greet --name=John --shout".to_string());
+    parser.add_command("name".to_string(), true, "n".to_string(), "Who to greet".to_string());
+    parser.add_command("shout".to_string(), false, "s".to_string(), "Greet loudly".to_string());
+    parser
+}
+
+fn greet(args: Vec<String>) -> String {
+    let map = parser().parse_vec(args).map().unwrap_or_default();
+    let greeting = format!("Hello, {}!", map.get("name").map(String::as_str).unwrap_or("world"));
+    if map.contains_key("shout") {
+        greeting.to_uppercase()
+    } else {
+        greeting
+    }
+}
+
+fn main() {
+    println!("{}", greet(std::env::args().skip(1).collect()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_greet() {
+        assert_eq!(greet(vec![]), "Hello, world!");
+        assert_eq!(greet(vec!["-n".to_string(), "John".to_string()]), "Hello, John!");
+        assert_eq!(greet(vec!["--name=John".to_string(), "--shout".to_string()]), "HELLO, JOHN!");
+    }
+}
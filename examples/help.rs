@@ -0,0 +1,42 @@
+//! A CLI that prints its help, or the help of a single option.
+//!
+//! ```text
+//! cargo run --example help -- --help
+//! cargo run --example help -- --help output
+//! ```
+
+use parsg::prelude::*;
+
+fn parser() -> Parser {
+    let mut parser = Parser::new("convert".to_string(), "Converts a file to another format".to_string(), "convert -i in.md -o out.html".to_string());
+    parser.add_command("input".to_string(), true, "i".to_string(), "The file to read".to_string()).display_order(0);
+    parser.add_command("output".to_string(), true, "o".to_string(), "The file to write".to_string()).display_order(1);
+    parser.add_command("verbose".to_string(), false, "v".to_string(), "Print progress".to_string());
+    parser
+}
+
+fn run(args: Vec<String>) -> String {
+    let result = parser().parse_vec(args);
+    match (result.help(), result.map()) {
+        (Some(help), _) => help,
+        (None, Some(map)) => format!("converting {:?} to {:?}", map.get("input"), map.get("output")),
+        (None, None) => result.error().unwrap_or_default(),
+    }
+}
+
+fn main() {
+    print!("{}", run(std::env::args().skip(1).collect()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help() {
+        let help = run(vec!["--help".to_string()]);
+        assert!(help.starts_with("Usage: convert [OPTIONS] ..."));
+        assert!(help.find("--input").unwrap() < help.find("--verbose").unwrap());
+        assert_eq!(run(vec!["-h".to_string(), "output".to_string()]), "-o --output: The file to write (takes input)\n");
+    }
+}
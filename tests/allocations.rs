@@ -0,0 +1,96 @@
+//! Tests of how much the parser allocates, run in their own binary so that only they use the counting allocator.
+
+use parsg::{Choices, ParseError, Parser};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts, per thread, the allocations and reallocations of at least `LARGE` bytes, so that a test can check how
+/// many times a large value is copied without other tests running at once getting in the way, and all the
+/// reallocations, to check how often collections grow.
+struct CountingAllocator;
+
+const LARGE: usize = 1 << 19;
+
+thread_local! {
+    static LARGE_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static REALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns how many reallocations `f` made on this thread.
+fn reallocations(f: impl FnOnce()) -> usize {
+    let before = REALLOCATIONS.with(|x| x.get());
+    f();
+    REALLOCATIONS.with(|x| x.get()) - before
+}
+
+fn count(size: usize) {
+    if size >= LARGE {
+        let _ = LARGE_ALLOCATIONS.try_with(|x| x.set(x.get() + 1));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        let _ = REALLOCATIONS.try_with(|x| x.set(x.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_large_value() {
+    let mut tester = Parser::new("test".to_string(), "A test program".to_string(), String::new());
+    tester.add_command("data".to_string(), true, "d".to_string(), "The data to send".to_string());
+    tester.add_command("mode".to_string(), true, "m".to_string(), "The mode".to_string())
+        .choices(Choices::Values(vec!["fast".to_string(), "slow".to_string()]));
+    let blob = "QUJD".repeat(1 << 18);
+
+    for input in [format!("--data={}", blob), format!("-d {}", blob), format!("--data \"{}\"", blob)] {
+        let before = LARGE_ALLOCATIONS.with(|x| x.get());
+        let result = tester.parse(input);
+        let copies = LARGE_ALLOCATIONS.with(|x| x.get()) - before;
+        // the argument split from the input, and the value taken from it, which only the map keeps
+        assert!(copies <= 2, "{} large allocations", copies);
+        assert_eq!(result.get_all("data").map(|x| x.len()), Some(1));
+        assert!(result.get_all("data").unwrap()[0] == blob);
+    }
+
+    let error = tester.parse(format!("--mode {}", blob)).parse_error().unwrap();
+    let value = format!("{}...", &blob[..1024]);
+    assert_eq!(error, ParseError::InvalidChoice { option: "mode".to_string(), value, suggestion: None });
+}
+
+#[test]
+fn test_with_capacity() {
+    let register = |tester: &mut Parser| {
+        for i in 0..100 {
+            tester.add_command(format!("option-{}", i), true, String::new(), String::new());
+        }
+    };
+    let mut grown = Parser::new("test".to_string(), String::new(), String::new());
+    let mut reserved = Parser::with_capacity("test".to_string(), String::new(), String::new(), 100);
+    let grown_reallocs = reallocations(|| register(&mut grown));
+    let reserved_reallocs = reallocations(|| register(&mut reserved));
+    assert!(reserved_reallocs < grown_reallocs, "{} reallocations with capacity, {} without", reserved_reallocs, grown_reallocs);
+    assert_eq!(reserved_reallocs, 0);
+
+    grown.shrink_to_fit();
+    let input = (0..100).map(|i| format!("--option-{}={}", i, i)).collect::<Vec<String>>().join(" ");
+    let mut result = None;
+    let parse_reallocs = reallocations(|| result = Some(grown.parse_str(&input)));
+    assert_eq!(result.unwrap().map().unwrap().len(), 100);
+    // only the list of arguments the input is split into grows, as its length is not known in advance
+    assert!(parse_reallocs < 8, "{} reallocations while parsing", parse_reallocs);
+}